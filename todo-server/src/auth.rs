@@ -0,0 +1,166 @@
+use crate::config::Config;
+use crate::{AppError, AppState};
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts, State},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqlitePool;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: i64,
+    exp: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterRequest {
+    email: String,
+    password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LoginRequest {
+    email: String,
+    password: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthResponse {
+    token: String,
+    expires_in_minutes: i64,
+}
+
+/// The authenticated user for a request, extracted from a validated JWT.
+pub struct AuthUser {
+    pub user_id: i64,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or(AppError::Unauthorized)?;
+
+        let token = header
+            .split_once(' ')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+            .map(|(_, token)| token)
+            .ok_or(AppError::Unauthorized)?;
+
+        let config = Config::from_ref(state);
+        let claims = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| AppError::Unauthorized)?
+        .claims;
+
+        Ok(AuthUser {
+            user_id: claims.sub,
+        })
+    }
+}
+
+pub async fn register(
+    State(pool): State<SqlitePool>,
+    State(config): State<Config>,
+    Json(body): Json<RegisterRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let password_hash = bcrypt::hash(&body.password, bcrypt::DEFAULT_COST)
+        .map_err(|err| AppError::Internal(err.into()))?;
+
+    let user_id = sqlx::query!(
+        r#"
+INSERT INTO users (email, password_hash)
+VALUES (?, ?)
+        "#,
+        body.email,
+        password_hash
+    )
+    .execute(&pool)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::BadRequest("email already registered".into())
+        }
+        other => other.into(),
+    })?
+    .last_insert_rowid();
+
+    let token = issue_token(user_id, &config)?;
+    Ok((
+        StatusCode::CREATED,
+        Json(AuthResponse {
+            token,
+            expires_in_minutes: config.jwt_expires_in_mins,
+        }),
+    ))
+}
+
+pub async fn login(
+    State(pool): State<SqlitePool>,
+    State(config): State<Config>,
+    Json(body): Json<LoginRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = sqlx::query!(
+        r#"
+SELECT id, password_hash
+FROM users
+WHERE email = ?
+        "#,
+        body.email
+    )
+    .fetch_optional(&pool)
+    .await?
+    .ok_or(AppError::Unauthorized)?;
+
+    let valid = bcrypt::verify(&body.password, &user.password_hash)
+        .map_err(|err| AppError::Internal(err.into()))?;
+    if !valid {
+        return Err(AppError::Unauthorized);
+    }
+
+    let token = issue_token(user.id, &config)?;
+    Ok(Json(AuthResponse {
+        token,
+        expires_in_minutes: config.jwt_expires_in_mins,
+    }))
+}
+
+fn issue_token(user_id: i64, config: &Config) -> Result<String, AppError> {
+    let expires_at = SystemTime::now()
+        .checked_add(std::time::Duration::from_secs(
+            (config.jwt_expires_in_mins * 60) as u64,
+        ))
+        .ok_or_else(|| AppError::Internal(anyhow::anyhow!("JWT expiry overflowed")))?
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| AppError::Internal(err.into()))?
+        .as_secs() as usize;
+
+    let claims = Claims {
+        sub: user_id,
+        exp: expires_at,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|err| AppError::Internal(err.into()))
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use clap::Parser;
+use std::net::SocketAddr;
+
+/// Server and database settings, sourced from CLI flags with environment
+/// variable fallbacks so the same binary works in both contexts.
+#[derive(Debug, Clone, Parser)]
+#[command(author, version, about)]
+pub struct Config {
+    /// SQLite connection string
+    #[arg(long, env = "DATABASE_URL")]
+    pub database_url: String,
+
+    /// Host/IP to bind the HTTP server to
+    #[arg(long, env = "HOST", default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to bind the HTTP server to
+    #[arg(long, env = "PORT", default_value_t = 3000)]
+    pub port: u16,
+
+    /// Tracing-subscriber filter directive
+    #[arg(
+        long,
+        env = "LOG_FILTER",
+        default_value = "todo_server=debug,tower_http=debug"
+    )]
+    pub log_filter: String,
+
+    /// Maximum number of pooled SQLite connections
+    #[arg(long, env = "DB_MAX_CONNECTIONS", default_value_t = 5)]
+    pub db_max_connections: u32,
+
+    /// Seconds to wait for a pooled connection before giving up
+    #[arg(long, env = "DB_CONNECT_TIMEOUT_SECS", default_value_t = 5)]
+    pub db_connect_timeout_secs: u64,
+
+    /// Secret used to sign and verify JWTs
+    #[arg(long, env = "JWT_SECRET")]
+    pub jwt_secret: String,
+
+    /// JWT lifetime, in minutes — also the value reported to clients
+    /// alongside the issued token
+    #[arg(long, env = "JWT_EXPIRES_IN", default_value_t = 60)]
+    pub jwt_expires_in_mins: i64,
+}
+
+impl Config {
+    pub fn socket_addr(&self) -> Result<SocketAddr> {
+        Ok(format!("{}:{}", self.host, self.port).parse()?)
+    }
+}
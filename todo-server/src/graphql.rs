@@ -0,0 +1,92 @@
+use crate::auth::AuthUser;
+use crate::{get_lists, List, Todo};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use async_graphql::{ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::State;
+use axum::response::{Html, IntoResponse};
+use sqlx::sqlite::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type AppSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Batch-loads todos for a set of list ids in a single query, so resolving
+/// `todos` for N lists costs one round trip instead of N.
+pub struct TodosLoader {
+    pool: SqlitePool,
+}
+
+#[async_trait::async_trait]
+impl Loader<i64> for TodosLoader {
+    type Value = Vec<Todo>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, list_ids: &[i64]) -> Result<HashMap<i64, Self::Value>, Self::Error> {
+        let mut query_builder =
+            sqlx::QueryBuilder::new("SELECT id, text, checked, list_id FROM todos WHERE list_id IN (");
+        let mut separated = query_builder.separated(", ");
+        for list_id in list_ids {
+            separated.push_bind(list_id);
+        }
+        query_builder.push(") ORDER BY id");
+
+        let todos: Vec<Todo> = query_builder
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Arc::new)?;
+
+        let mut grouped: HashMap<i64, Vec<Todo>> = HashMap::new();
+        for todo in todos {
+            grouped.entry(todo.list_id).or_default().push(todo);
+        }
+
+        Ok(grouped)
+    }
+}
+
+#[ComplexObject]
+impl List {
+    async fn todos(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Todo>> {
+        let loader = ctx.data::<DataLoader<TodosLoader>>()?;
+        Ok(loader.load_one(self.id).await?.unwrap_or_default())
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn lists(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<List>> {
+        let pool = ctx.data::<SqlitePool>()?;
+        let owner_id = ctx.data::<i64>()?;
+        let lists = get_lists(pool, *owner_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+        Ok(lists)
+    }
+}
+
+pub fn build_schema(pool: SqlitePool) -> AppSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(DataLoader::new(
+            TodosLoader { pool: pool.clone() },
+            tokio::spawn,
+        ))
+        .data(pool)
+        .finish()
+}
+
+pub async fn graphql_handler(
+    AuthUser { user_id }: AuthUser,
+    State(schema): State<AppSchema>,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(req.into_inner().data(user_id)).await.into()
+}
+
+pub async fn graphql_playground() -> impl IntoResponse {
+    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+}
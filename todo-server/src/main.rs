@@ -1,35 +1,44 @@
+mod auth;
+mod config;
+mod graphql;
+
 use anyhow::Result;
+use auth::AuthUser;
 use axum::{
-    extract::{Path, State},
+    extract::{FromRef, Path, State},
     http::StatusCode,
     response::{IntoResponse, Response},
-    routing::get,
+    routing::{get, patch, post},
     Json, Router,
 };
+use clap::Parser;
+use config::Config;
+use graphql::AppSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sqlx::{sqlite::SqlitePool, Sqlite, Pool};
-use std::env;
-use std::net::SocketAddr;
+use sqlx::sqlite::{
+    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions,
+};
+use std::str::FromStr;
+use std::time::Duration;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let pool = SqlitePool::connect(&env::var("DATABASE_URL")?).await?;
+    let config = Config::parse();
+
+    let pool = connect_pool(&config).await?;
     sqlx::migrate!().run(&pool).await?;
 
     tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG")
-                .unwrap_or_else(|_| "todo_server=debug,tower_http=debug".into()),
-        ))
+        .with(tracing_subscriber::EnvFilter::new(config.log_filter.clone()))
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    let app = app(pool);
+    let addr = config.socket_addr()?;
+    let app = app(pool, config);
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
     tracing::debug!("listening on {}", addr);
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
@@ -38,40 +47,204 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn app(pool: SqlitePool) -> Router<Pool<Sqlite>> {
-    Router::with_state(pool)
-            .route("/lists", get(handle_get_lists))
-            .route("/lists/:id/todos", get(handle_get_todos))
+async fn connect_pool(config: &Config) -> Result<SqlitePool> {
+    let connect_options = SqliteConnectOptions::from_str(&config.database_url)?
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .auto_vacuum(SqliteAutoVacuum::Incremental);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(config.db_max_connections)
+        .connect_timeout(Duration::from_secs(config.db_connect_timeout_secs))
+        .connect_with(connect_options)
+        .await?;
+
+    Ok(pool)
+}
+
+#[derive(Clone)]
+struct AppState {
+    pool: SqlitePool,
+    config: Config,
+    schema: AppSchema,
+}
+
+impl FromRef<AppState> for SqlitePool {
+    fn from_ref(state: &AppState) -> SqlitePool {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for Config {
+    fn from_ref(state: &AppState) -> Config {
+        state.config.clone()
+    }
+}
+
+impl FromRef<AppState> for AppSchema {
+    fn from_ref(state: &AppState) -> AppSchema {
+        state.schema.clone()
+    }
+}
+
+fn app(pool: SqlitePool, config: Config) -> Router<AppState> {
+    let schema = graphql::build_schema(pool.clone());
+
+    Router::with_state(AppState { pool, config, schema })
+            .route("/auth/register", post(auth::register))
+            .route("/auth/login", post(auth::login))
+            .route("/lists", get(handle_get_lists).post(handle_create_list))
+            .route(
+                "/lists/:id",
+                patch(handle_update_list).delete(handle_delete_list),
+            )
+            .route(
+                "/lists/:id/todos",
+                get(handle_get_todos).post(handle_create_todo),
+            )
+            .route(
+                "/todos/:id",
+                patch(handle_patch_todo)
+                    .put(handle_put_todo)
+                    .delete(handle_delete_todo),
+            )
+            .route(
+                "/graphql",
+                get(graphql::graphql_playground).post(graphql::graphql_handler),
+            )
+            .route("/health", get(handle_health))
+            .route("/health/db", get(handle_health_db))
             .layer(TraceLayer::new_for_http())
 }
 
-async fn handle_get_lists(State(pool): State<SqlitePool>) -> Result<impl IntoResponse, AppError> {
-    let lists = get_lists(&pool).await?;
+async fn handle_get_lists(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+) -> Result<impl IntoResponse, AppError> {
+    let lists = get_lists(&pool, auth.user_id).await?;
     Ok(Json(lists))
 }
 
+async fn handle_create_list(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Json(body): Json<CreateList>,
+) -> Result<impl IntoResponse, AppError> {
+    let list = insert_list(&pool, auth.user_id, body).await?;
+    Ok((StatusCode::CREATED, Json(list)))
+}
+
+async fn handle_update_list(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(body): Json<UpdateList>,
+) -> Result<impl IntoResponse, AppError> {
+    let list = update_list(&pool, id, auth.user_id, body).await?;
+    Ok(Json(list))
+}
+
+async fn handle_delete_list(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    delete_list(&pool, id, auth.user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn handle_get_todos(
+    auth: AuthUser,
     State(pool): State<SqlitePool>,
-    Path(list_id): Path<i32>,
+    Path(list_id): Path<i64>,
 ) -> Result<impl IntoResponse, AppError> {
-    let todos = get_todos(&pool, list_id).await?;
+    let todos = get_todos(&pool, list_id, auth.user_id).await?;
     Ok(Json(todos))
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+async fn handle_create_todo(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(list_id): Path<i64>,
+    Json(body): Json<CreateTodo>,
+) -> Result<impl IntoResponse, AppError> {
+    let todo = insert_todo(&pool, list_id, auth.user_id, body).await?;
+    Ok((StatusCode::CREATED, Json(todo)))
+}
+
+async fn handle_patch_todo(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(body): Json<UpdateTodo>,
+) -> Result<impl IntoResponse, AppError> {
+    let todo = update_todo(&pool, id, auth.user_id, body).await?;
+    Ok(Json(todo))
+}
+
+async fn handle_put_todo(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+    Json(body): Json<PutTodo>,
+) -> Result<impl IntoResponse, AppError> {
+    let todo = upsert_todo(&pool, id, auth.user_id, body).await?;
+    Ok(Json(todo))
+}
+
+async fn handle_delete_todo(
+    auth: AuthUser,
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    delete_todo(&pool, id, auth.user_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Liveness probe: always 200 once the process is up and routing requests.
+async fn handle_health() -> impl IntoResponse {
+    Json(json!({}))
+}
+
+/// Readiness probe: 200 only if a trivial query round-trips through the pool.
+async fn handle_health_db(State(pool): State<SqlitePool>) -> impl IntoResponse {
+    match sqlx::query("SELECT 1").execute(&pool).await {
+        Ok(_) => (StatusCode::OK, Json(json!({}))),
+        Err(err) => {
+            tracing::error!("db health check failed: {err}");
+            (StatusCode::SERVICE_UNAVAILABLE, Json(json!({})))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[graphql(complex)]
 struct List {
     id: i64,
     name: String,
+    owner_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateList {
+    name: String,
 }
 
-async fn get_lists(pool: &SqlitePool) -> Result<Vec<List>> {
+#[derive(Debug, Deserialize)]
+struct UpdateList {
+    name: String,
+}
+
+async fn get_lists(pool: &SqlitePool, owner_id: i64) -> Result<Vec<List>, AppError> {
     let result = sqlx::query_as!(
         List,
         r#"
-SELECT id, name
+SELECT id, name, owner_id
 FROM lists
+WHERE owner_id = ?
 ORDER BY id
-        "#
+        "#,
+        owner_id
     )
     .fetch_all(pool)
     .await?;
@@ -79,7 +252,96 @@ ORDER BY id
     Ok(result)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Fetches a list and verifies it belongs to `owner_id`, distinguishing a
+/// missing list (404) from one owned by someone else (403).
+async fn get_owned_list(pool: &SqlitePool, id: i64, owner_id: i64) -> Result<List, AppError> {
+    let list = sqlx::query_as!(
+        List,
+        r#"
+SELECT id, name, owner_id
+FROM lists
+WHERE id = ?
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    if list.owner_id != owner_id {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(list)
+}
+
+async fn insert_list(pool: &SqlitePool, owner_id: i64, body: CreateList) -> Result<List, AppError> {
+    if body.name.trim().is_empty() {
+        return Err(AppError::Validation("name must not be empty".into()));
+    }
+
+    let result = sqlx::query_as!(
+        List,
+        r#"
+INSERT INTO lists (name, owner_id)
+VALUES (?, ?)
+RETURNING id, name, owner_id
+        "#,
+        body.name,
+        owner_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn update_list(
+    pool: &SqlitePool,
+    id: i64,
+    owner_id: i64,
+    body: UpdateList,
+) -> Result<List, AppError> {
+    if body.name.trim().is_empty() {
+        return Err(AppError::Validation("name must not be empty".into()));
+    }
+
+    get_owned_list(pool, id, owner_id).await?;
+
+    let result = sqlx::query_as!(
+        List,
+        r#"
+UPDATE lists
+SET name = ?
+WHERE id = ?
+RETURNING id, name, owner_id
+        "#,
+        body.name,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn delete_list(pool: &SqlitePool, id: i64, owner_id: i64) -> Result<(), AppError> {
+    get_owned_list(pool, id, owner_id).await?;
+
+    sqlx::query!(
+        r#"
+DELETE FROM lists
+WHERE id = ?
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, async_graphql::SimpleObject)]
 struct Todo {
     id: i64,
     text: String,
@@ -87,7 +349,29 @@ struct Todo {
     list_id: i64,
 }
 
-async fn get_todos(pool: &SqlitePool, list_id: i32) -> Result<Vec<Todo>> {
+#[derive(Debug, Deserialize)]
+struct CreateTodo {
+    text: String,
+    #[serde(default)]
+    checked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateTodo {
+    text: Option<String>,
+    checked: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PutTodo {
+    text: String,
+    checked: bool,
+    list_id: i64,
+}
+
+async fn get_todos(pool: &SqlitePool, list_id: i64, owner_id: i64) -> Result<Vec<Todo>, AppError> {
+    get_owned_list(pool, list_id, owner_id).await?;
+
     let result = sqlx::query_as!(
         Todo,
         r#"
@@ -104,21 +388,199 @@ ORDER BY id
     Ok(result)
 }
 
-enum AppError {
-    InternalServerError(anyhow::Error),
+/// Fetches a todo and verifies the list it belongs to is owned by
+/// `owner_id`, distinguishing a missing todo (404) from one owned by
+/// someone else (403).
+async fn get_owned_todo(pool: &SqlitePool, id: i64, owner_id: i64) -> Result<Todo, AppError> {
+    let row = sqlx::query!(
+        r#"
+SELECT todos.id as "id!", todos.text as "text!", todos.checked as "checked!", todos.list_id as "list_id!", lists.owner_id as "owner_id!"
+FROM todos
+JOIN lists ON lists.id = todos.list_id
+WHERE todos.id = ?
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+
+    if row.owner_id != owner_id {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(Todo {
+        id: row.id,
+        text: row.text,
+        checked: row.checked,
+        list_id: row.list_id,
+    })
 }
 
-impl From<anyhow::Error> for AppError {
-    fn from(inner: anyhow::Error) -> Self {
-        AppError::InternalServerError(inner)
+async fn insert_todo(
+    pool: &SqlitePool,
+    list_id: i64,
+    owner_id: i64,
+    body: CreateTodo,
+) -> Result<Todo, AppError> {
+    if body.text.trim().is_empty() {
+        return Err(AppError::Validation("text must not be empty".into()));
+    }
+
+    get_owned_list(pool, list_id, owner_id).await?;
+
+    let result = sqlx::query_as!(
+        Todo,
+        r#"
+INSERT INTO todos (text, checked, list_id)
+VALUES (?, ?, ?)
+RETURNING id, text, checked, list_id
+        "#,
+        body.text,
+        body.checked,
+        list_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn update_todo(
+    pool: &SqlitePool,
+    id: i64,
+    owner_id: i64,
+    body: UpdateTodo,
+) -> Result<Todo, AppError> {
+    if let Some(text) = &body.text {
+        if text.trim().is_empty() {
+            return Err(AppError::Validation("text must not be empty".into()));
+        }
+    }
+
+    let existing = get_owned_todo(pool, id, owner_id).await?;
+
+    let text = body.text.unwrap_or(existing.text);
+    let checked = body.checked.unwrap_or(existing.checked);
+
+    let result = sqlx::query_as!(
+        Todo,
+        r#"
+UPDATE todos
+SET text = ?, checked = ?
+WHERE id = ?
+RETURNING id, text, checked, list_id
+        "#,
+        text,
+        checked,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn upsert_todo(
+    pool: &SqlitePool,
+    id: i64,
+    owner_id: i64,
+    body: PutTodo,
+) -> Result<Todo, AppError> {
+    if body.text.trim().is_empty() {
+        return Err(AppError::Validation("text must not be empty".into()));
+    }
+
+    get_owned_list(pool, body.list_id, owner_id).await?;
+
+    match get_owned_todo(pool, id, owner_id).await {
+        Ok(_) | Err(AppError::NotFound) => {}
+        Err(err) => return Err(err),
+    }
+
+    let result = sqlx::query_as!(
+        Todo,
+        r#"
+INSERT INTO todos (id, text, checked, list_id)
+VALUES (?, ?, ?, ?)
+ON CONFLICT (id) DO UPDATE SET text = excluded.text, checked = excluded.checked, list_id = excluded.list_id
+RETURNING id, text, checked, list_id
+        "#,
+        id,
+        body.text,
+        body.checked,
+        body.list_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result)
+}
+
+async fn delete_todo(pool: &SqlitePool, id: i64, owner_id: i64) -> Result<(), AppError> {
+    get_owned_todo(pool, id, owner_id).await?;
+
+    sqlx::query!(
+        r#"
+DELETE FROM todos
+WHERE id = ?
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AppError {
+    #[error("resource not found")]
+    NotFound,
+    #[error("bad request: {0}")]
+    BadRequest(String),
+    #[error("validation error: {0}")]
+    Validation(String),
+    #[error("authentication required")]
+    Unauthorized,
+    #[error("you do not have access to this resource")]
+    Forbidden,
+    #[error("database error: {0}")]
+    Database(sqlx::Error),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(inner: sqlx::Error) -> Self {
+        match inner {
+            sqlx::Error::RowNotFound => AppError::NotFound,
+            other => AppError::Database(other),
+        }
     }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::InternalServerError(_inner) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, "Something went wrong")
+        let (status, error_message) = match &self {
+            AppError::NotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            AppError::BadRequest(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AppError::Validation(_) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
+            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, self.to_string()),
+            AppError::Database(inner) => {
+                tracing::error!("database error: {inner}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Something went wrong".to_string(),
+                )
+            }
+            AppError::Internal(inner) => {
+                tracing::error!("internal error: {inner}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Something went wrong".to_string(),
+                )
             }
         };
 
@@ -140,15 +602,54 @@ mod tests {
     use serde_json::{json, Value};
     use tower::ServiceExt;
 
+    fn test_config() -> Config {
+        Config {
+            database_url: "sqlite::memory:".into(),
+            host: "127.0.0.1".into(),
+            port: 3000,
+            log_filter: "todo_server=debug,tower_http=debug".into(),
+            db_max_connections: 5,
+            db_connect_timeout_secs: 5,
+            jwt_secret: "test-secret".into(),
+            jwt_expires_in_mins: 60,
+        }
+    }
+
+    async fn register_and_login(app: &Router<AppState>) -> String {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/auth/register")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({ "email": "user@example.com", "password": "hunter2" }).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        body_json(response).await["token"].as_str().unwrap().to_string()
+    }
+
     #[tokio::test]
     async fn test_lists() {
         let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
         sqlx::migrate!().run(&pool).await.unwrap();
 
-        let app = app(pool);
+        let app = app(pool, test_config());
+        let token = register_and_login(&app).await;
 
         let response = app
-            .oneshot(Request::builder().uri("/lists").body(Body::empty()).unwrap())
+            .oneshot(
+                Request::builder()
+                    .uri("/lists")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
             .await
             .unwrap();
 
@@ -158,4 +659,275 @@ mod tests {
         let body: Value = serde_json::from_slice(&body).unwrap();
         assert_eq!(body, json!([]));
     }
+
+    #[tokio::test]
+    async fn test_lists_require_auth() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+
+        let response = app
+            .oneshot(Request::builder().uri("/lists").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_crud() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+        let token = register_and_login(&app).await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/lists")
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "name": "groceries" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = body_json(response).await;
+        let id = created["id"].as_i64().unwrap();
+        assert_eq!(created["name"], "groceries");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri(format!("/lists/{id}"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "name": "shopping" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["name"], "shopping");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/lists/{id}"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_create_list_rejects_blank_name() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+        let token = register_and_login(&app).await;
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/lists")
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "name": "   " }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn test_todo_crud() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+        let token = register_and_login(&app).await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/lists")
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "name": "groceries" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let list_id = body_json(response).await["id"].as_i64().unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/lists/{list_id}/todos"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "text": "buy milk" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let todo = body_json(response).await;
+        let todo_id = todo["id"].as_i64().unwrap();
+        assert_eq!(todo["checked"], false);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PATCH")
+                    .uri(format!("/todos/{todo_id}"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "checked": true }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let todo = body_json(response).await;
+        assert_eq!(todo["checked"], true);
+        assert_eq!(todo["text"], "buy milk");
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("PUT")
+                    .uri(format!("/todos/{todo_id}"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({ "text": "buy oat milk", "checked": false, "list_id": list_id })
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body_json(response).await["text"], "buy oat milk");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/todos/{todo_id}"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoints() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health/db")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_list_not_accessible_to_other_user() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!().run(&pool).await.unwrap();
+
+        let app = app(pool, test_config());
+        let owner_token = register_and_login(&app).await;
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/lists")
+                    .header("authorization", format!("Bearer {owner_token}"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(json!({ "name": "groceries" }).to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let list_id = body_json(response).await["id"].as_i64().unwrap();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/auth/register")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        json!({ "email": "other@example.com", "password": "hunter2" })
+                            .to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let other_token = body_json(response).await["token"].as_str().unwrap().to_string();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri(format!("/lists/{list_id}"))
+                    .header("authorization", format!("Bearer {other_token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
 }
\ No newline at end of file